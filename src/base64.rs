@@ -0,0 +1,134 @@
+use thiserror::Error;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const PADDING: char = '=';
+
+#[derive(Error, Debug)]
+pub enum Base64DecodeError {
+    #[error("expected a length that is a multiple of 4, got {0}")]
+    InvalidLength(usize),
+    #[error("invalid base64 character {0:?}")]
+    InvalidCharacter(char),
+}
+
+fn sextet_to_char(sextet: u8) -> char {
+    ALPHABET[sextet as usize] as char
+}
+
+fn char_to_sextet(char: char) -> Result<u8, Base64DecodeError> {
+    match char {
+        'A'..='Z' => Ok(char as u8 - b'A'),
+        'a'..='z' => Ok(char as u8 - b'a' + 26),
+        '0'..='9' => Ok(char as u8 - b'0' + 52),
+        '+' => Ok(62),
+        '/' => Ok(63),
+        _ => Err(Base64DecodeError::InvalidCharacter(char)),
+    }
+}
+
+pub fn encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for group in bytes.chunks(3) {
+        let b0 = group[0];
+        let b1 = *group.get(1).unwrap_or(&0);
+        let b2 = *group.get(2).unwrap_or(&0);
+
+        let triplet = (b0 as u32) << 16 | (b1 as u32) << 8 | b2 as u32;
+
+        encoded.push(sextet_to_char((triplet >> 18 & 0x3f) as u8));
+        encoded.push(sextet_to_char((triplet >> 12 & 0x3f) as u8));
+        encoded.push(if group.len() > 1 {
+            sextet_to_char((triplet >> 6 & 0x3f) as u8)
+        } else {
+            PADDING
+        });
+        encoded.push(if group.len() > 2 {
+            sextet_to_char((triplet & 0x3f) as u8)
+        } else {
+            PADDING
+        });
+    }
+
+    encoded
+}
+
+pub fn decode(data: &str) -> Result<Vec<u8>, Base64DecodeError> {
+    if data.len() % 4 != 0 {
+        return Err(Base64DecodeError::InvalidLength(data.len()));
+    }
+
+    let chars: Vec<char> = data.chars().collect();
+    let mut decoded = Vec::with_capacity(chars.len() / 4 * 3);
+
+    for group in chars.chunks(4) {
+        let padding = group.iter().filter(|&&char| char == PADDING).count();
+
+        let sextets: Vec<u8> = group
+            .iter()
+            .map(|&char| {
+                if char == PADDING {
+                    Ok(0)
+                } else {
+                    char_to_sextet(char)
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        let triplet = (sextets[0] as u32) << 18
+            | (sextets[1] as u32) << 12
+            | (sextets[2] as u32) << 6
+            | sextets[3] as u32;
+
+        decoded.push((triplet >> 16) as u8);
+        if padding < 2 {
+            decoded.push((triplet >> 8) as u8);
+        }
+        if padding < 1 {
+            decoded.push(triplet as u8);
+        }
+    }
+
+    Ok(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_empty() {
+        assert_eq!(encode(b""), "");
+    }
+
+    #[test]
+    fn test_encode_one_byte() {
+        assert_eq!(encode(b"M"), "TQ==");
+    }
+
+    #[test]
+    fn test_encode_two_bytes() {
+        assert_eq!(encode(b"Ma"), "TWE=");
+    }
+
+    #[test]
+    fn test_encode_three_bytes() {
+        assert_eq!(encode(b"Man"), "TWFu");
+    }
+
+    #[test]
+    fn test_decode_roundtrip() {
+        let bytes = b"This is where your secret message will be!";
+        assert_eq!(decode(&encode(bytes)).unwrap(), bytes);
+    }
+
+    #[test]
+    fn test_decode_invalid_length() {
+        assert!(decode("TQ=").is_err());
+    }
+
+    #[test]
+    fn test_decode_invalid_character() {
+        assert!(decode("T!==").is_err());
+    }
+}
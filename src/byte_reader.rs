@@ -0,0 +1,103 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ByteReaderError {
+    #[error("expected {expected} more byte(s), only {remaining} remaining")]
+    OutOfBounds { expected: usize, remaining: usize },
+}
+
+/// A cursor over a byte slice that hands out fixed-size reads, failing with
+/// a descriptive error instead of panicking when the slice runs out.
+pub struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> ByteReader<'a> {
+        ByteReader { bytes, position: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.position
+    }
+
+    pub fn take(&mut self, n: usize) -> Result<&'a [u8], ByteReaderError> {
+        if self.remaining() < n {
+            return Err(ByteReaderError::OutOfBounds {
+                expected: n,
+                remaining: self.remaining(),
+            });
+        }
+
+        let slice = &self.bytes[self.position..self.position + n];
+        self.position += n;
+
+        Ok(slice)
+    }
+
+    pub fn u32_be(&mut self) -> Result<u32, ByteReaderError> {
+        let bytes: [u8; 4] = self.take(4)?.try_into().unwrap();
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    pub fn u16_be(&mut self) -> Result<u16, ByteReaderError> {
+        let bytes: [u8; 2] = self.take(2)?.try_into().unwrap();
+        Ok(u16::from_be_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take() {
+        let mut reader = ByteReader::new(&[1, 2, 3, 4]);
+        assert_eq!(reader.take(2).unwrap(), &[1, 2]);
+        assert_eq!(reader.take(2).unwrap(), &[3, 4]);
+    }
+
+    #[test]
+    fn test_take_out_of_bounds() {
+        let mut reader = ByteReader::new(&[1, 2]);
+        assert!(reader.take(3).is_err());
+    }
+
+    #[test]
+    fn test_u32_be() {
+        let mut reader = ByteReader::new(&[0, 0, 1, 0]);
+        assert_eq!(reader.u32_be().unwrap(), 256);
+    }
+
+    #[test]
+    fn test_u32_be_out_of_bounds() {
+        let mut reader = ByteReader::new(&[0, 0, 1]);
+        assert!(reader.u32_be().is_err());
+    }
+
+    #[test]
+    fn test_u16_be() {
+        let mut reader = ByteReader::new(&[1, 0]);
+        assert_eq!(reader.u16_be().unwrap(), 256);
+    }
+
+    #[test]
+    fn test_remaining() {
+        let mut reader = ByteReader::new(&[1, 2, 3]);
+        assert_eq!(reader.remaining(), 3);
+        reader.take(1).unwrap();
+        assert_eq!(reader.remaining(), 2);
+    }
+
+    #[test]
+    fn test_position() {
+        let mut reader = ByteReader::new(&[1, 2, 3]);
+        reader.take(2).unwrap();
+        assert_eq!(reader.position(), 2);
+    }
+}
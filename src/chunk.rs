@@ -7,7 +7,10 @@ use anyhow::Result;
 use crc32fast::Hasher;
 use thiserror::Error;
 
+use super::base64::{self, Base64DecodeError};
+use super::byte_reader::{ByteReader, ByteReaderError};
 use super::chunk_type::{PNG_CHUNK_TYPE_LENGTH, PngChunkType, PngChunkTypeParsingError};
+use super::metadata::{self, Header, MetadataDecodeError, MetadataEncodeError};
 
 #[derive(Debug)]
 pub struct PngChunk {
@@ -23,12 +26,8 @@ pub const PNG_CHUNK_MINIMUM_LENGTH: usize =
 
 #[derive(Error, Debug)]
 pub enum PngChunkParsingError {
-    #[error(
-        "expected at least {PNG_CHUNK_MINIMUM_LENGTH} bytes (length, chunk type and crc), got {0}"
-    )]
-    InvalidMinimumLength(usize),
-    #[error("expected data of length {expected}, got {got}")]
-    InvalidDataLength { expected: usize, got: usize },
+    #[error(transparent)]
+    OutOfBounds(#[from] ByteReaderError),
     #[error("expected crc {expected}, got {got}")]
     InvalidCRC { expected: u32, got: u32 },
     #[error(transparent)]
@@ -39,30 +38,14 @@ impl TryFrom<&[u8]> for PngChunk {
     type Error = PngChunkParsingError;
 
     fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
-        if bytes.len() < PNG_CHUNK_MINIMUM_LENGTH {
-            return Err(PngChunkParsingError::InvalidMinimumLength(bytes.len()));
-        }
-
-        let data_length_bytes: [u8; 4] = bytes[0..PNG_CHUNK_DATA_LENGTH_LENGTH].try_into().unwrap();
-        let data_length = u32::from_be_bytes(data_length_bytes);
+        let mut reader = ByteReader::new(bytes);
 
-        if bytes.len() < (data_length as usize) + PNG_CHUNK_TYPE_LENGTH + CRC_LENGTH {
-            return Err(PngChunkParsingError::InvalidDataLength {
-                expected: data_length as usize,
-                got: bytes.len(),
-            });
-        }
-
-        let chunk_type_bytes: [u8; 4] = bytes[4..8].try_into().unwrap();
+        let data_length = reader.u32_be()?;
+        let chunk_type_bytes: [u8; PNG_CHUNK_TYPE_LENGTH] =
+            reader.take(PNG_CHUNK_TYPE_LENGTH)?.try_into().unwrap();
         let chunk_type = PngChunkType::try_from(chunk_type_bytes)?;
-
-        let data_last_index = 8 + data_length as usize;
-        let data = bytes[8..data_last_index].to_vec();
-
-        let crc_bytes: [u8; 4] = bytes[data_last_index..(data_last_index + 4)]
-            .try_into()
-            .unwrap();
-        let crc = u32::from_be_bytes(crc_bytes);
+        let data = reader.take(data_length as usize)?.to_vec();
+        let crc = reader.u32_be()?;
 
         let chunk = PngChunk {
             length: data_length,
@@ -118,6 +101,28 @@ impl PngChunk {
         String::from_utf8(self.data.clone())
     }
 
+    pub fn data_as_base64(&self) -> String {
+        base64::encode(&self.data)
+    }
+
+    pub fn from_base64(
+        chunk_type: PngChunkType,
+        data: &str,
+    ) -> Result<PngChunk, Base64DecodeError> {
+        Ok(PngChunk::new(chunk_type, base64::decode(data)?))
+    }
+
+    pub fn new_metadata(
+        chunk_type: PngChunkType,
+        header: &Header,
+    ) -> Result<PngChunk, MetadataEncodeError> {
+        Ok(PngChunk::new(chunk_type, metadata::encode(header)?))
+    }
+
+    pub fn metadata(&self) -> Result<Header, MetadataDecodeError> {
+        metadata::decode(&self.data)
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
         self.length
             .to_be_bytes()
@@ -239,6 +244,45 @@ mod tests {
         assert!(chunk.is_err());
     }
 
+    #[test]
+    fn test_chunk_data_as_base64() {
+        let chunk = testing_chunk();
+        assert_eq!(
+            chunk.data_as_base64(),
+            base64::encode(b"This is where your secret message will be!")
+        );
+    }
+
+    #[test]
+    fn test_chunk_from_base64() {
+        let chunk_type = PngChunkType::from_str("RuSt").unwrap();
+        let encoded = base64::encode(b"binary payload");
+
+        let chunk = PngChunk::from_base64(chunk_type, &encoded).unwrap();
+
+        assert_eq!(chunk.data, b"binary payload");
+    }
+
+    #[test]
+    fn test_chunk_from_base64_invalid() {
+        let chunk_type = PngChunkType::from_str("RuSt").unwrap();
+        assert!(PngChunk::from_base64(chunk_type, "not valid base64!").is_err());
+    }
+
+    #[test]
+    fn test_chunk_new_metadata_roundtrip() {
+        let chunk_type = PngChunkType::from_str("meTa").unwrap();
+        let mut header = Header::new();
+        header.insert(
+            "author".to_string(),
+            crate::metadata::MetaValue::String("rust".to_string()),
+        );
+
+        let chunk = PngChunk::new_metadata(chunk_type, &header).unwrap();
+
+        assert_eq!(chunk.metadata().unwrap(), header);
+    }
+
     #[test]
     pub fn test_chunk_trait_impls() {
         let data_length: u32 = 42;
@@ -5,6 +5,8 @@ use std::{
 
 use thiserror::Error;
 
+use super::byte_reader::ByteReader;
+
 pub const PNG_CHUNK_TYPE_LENGTH: usize = 4;
 
 pub type PngChunkTypeBinaryData = [u8; PNG_CHUNK_TYPE_LENGTH];
@@ -51,12 +53,13 @@ impl FromStr for PngChunkType {
             });
         }
 
-        let chars = &mut str.chars();
+        let bytes: PngChunkTypeBinaryData = ByteReader::new(str.as_bytes())
+            .take(PNG_CHUNK_TYPE_LENGTH)
+            .expect("length already checked above")
+            .try_into()
+            .unwrap();
 
-        PngChunkType::try_from(
-            [chars.next(), chars.next(), chars.next(), chars.next()]
-                .map(|maybe_char| maybe_char.unwrap() as u8),
-        )
+        PngChunkType::try_from(bytes)
     }
 }
 
@@ -79,28 +82,23 @@ impl PngChunkType {
         self.bytes().iter().all(|&byte| is_ascii_letter_byte(byte))
     }
 
-    #[allow(unused)]
-    fn is_valid(&self) -> bool {
+    pub fn is_valid(&self) -> bool {
         self.0[2].is_uppercase() && self.is_valid_chars()
     }
 
-    #[allow(unused)]
-    fn is_critical(&self) -> bool {
+    pub fn is_critical(&self) -> bool {
         self.0[0].is_uppercase()
     }
 
-    #[allow(unused)]
-    fn is_public(&self) -> bool {
+    pub fn is_public(&self) -> bool {
         self.0[1].is_uppercase()
     }
 
-    #[allow(unused)]
-    fn is_reserved_bit_valid(&self) -> bool {
+    pub fn is_reserved_bit_valid(&self) -> bool {
         self.0[2].is_uppercase()
     }
 
-    #[allow(unused)]
-    fn is_safe_to_copy(&self) -> bool {
+    pub fn is_safe_to_copy(&self) -> bool {
         self.0[3].is_lowercase()
     }
 }
@@ -0,0 +1,9 @@
+pub mod base64;
+pub mod byte_reader;
+pub mod chunk;
+pub mod chunk_type;
+pub mod metadata;
+pub mod png;
+pub mod stream;
+
+pub use png::Png;
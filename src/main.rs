@@ -6,8 +6,10 @@ use std::{
 use clap::Parser;
 use pngme::{
     Png,
+    base64 as base64_codec,
     chunk::PngChunk,
     chunk_type::{PngChunkType, PngChunkTypeBinaryData},
+    metadata::{Header, MetaValue},
 };
 
 /// PNG message encoder
@@ -31,12 +33,20 @@ enum Commands {
         chunk_type: String,
 
         /// The message
-        #[arg(required = true)]
-        message: String,
+        #[arg(required_unless_present_any = ["input_file", "meta"])]
+        message: Option<String>,
 
         /// Alternative path to write modified content
         #[arg(required = false)]
         output_file_path: Option<String>,
+
+        /// Reads the message from a file instead, so arbitrary binary payloads can be hidden
+        #[arg(long, conflicts_with_all = ["message", "meta"])]
+        input_file: Option<String>,
+
+        /// Attaches a `key=value` metadata field instead of a flat message (repeatable)
+        #[arg(long = "meta", conflicts_with_all = ["message", "input_file"])]
+        meta: Vec<String>,
     },
     /// Decodes a possibly existing message in a PNG file under a chunk with a certain type
     Decode {
@@ -47,6 +57,14 @@ enum Commands {
         /// The chunk type under which the message should have been encoded
         #[arg(required = true)]
         chunk_type: String,
+
+        /// Prints the chunk bytes as base64 instead of interpreting them as utf-8 text
+        #[arg(long)]
+        base64: bool,
+
+        /// Interprets the chunk as metadata and pretty-prints its fields
+        #[arg(long)]
+        meta: bool,
     },
     /// Removes a chunk with a certain type from a PNG file (useful when you want to remove a message)
     Remove {
@@ -82,11 +100,26 @@ fn create_png_from_file_bytes(file: &mut File) -> anyhow::Result<Png> {
     Ok(png)
 }
 
+fn parse_meta_entries(entries: Vec<String>) -> anyhow::Result<Header> {
+    let mut header = Header::new();
+
+    for entry in entries {
+        let (key, value) = entry
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("expected --meta entries in key=value form, got {entry:?}"))?;
+        header.insert(key.to_string(), MetaValue::String(value.to_string()));
+    }
+
+    Ok(header)
+}
+
 fn handle_encode_command(
     file_path: String,
     chunk_type: String,
-    message: String,
+    message: Option<String>,
     output_file_path: Option<String>,
+    input_file: Option<String>,
+    meta: Vec<String>,
 ) -> anyhow::Result<()> {
     let mut file = File::open(&file_path)?;
     let mut png = create_png_from_file_bytes(&mut file)?;
@@ -94,10 +127,30 @@ fn handle_encode_command(
     png.remove_first_chunk(&chunk_type).ok();
 
     let chunk_type_bytes: PngChunkTypeBinaryData = chunk_type.as_bytes().try_into().unwrap();
-    png.append_chunk(PngChunk::new(
-        PngChunkType::try_from(chunk_type_bytes)?,
-        message.as_bytes().to_vec(),
-    ));
+    let chunk_type = PngChunkType::try_from(chunk_type_bytes)?;
+
+    if !chunk_type.is_critical() && !chunk_type.is_safe_to_copy() {
+        eprintln!(
+            "warning: chunk type {chunk_type} does not have the safe-to-copy bit set, some PNG editors may drop it"
+        );
+    }
+
+    let chunk = if !meta.is_empty() {
+        PngChunk::new_metadata(chunk_type, &parse_meta_entries(meta)?)?
+    } else {
+        let data = match input_file {
+            Some(input_file_path) => {
+                let mut content = Vec::new();
+                File::open(input_file_path)?.read_to_end(&mut content)?;
+                content
+            }
+            None => message.expect("message or input file is required").into_bytes(),
+        };
+
+        PngChunk::new(chunk_type, data)
+    };
+
+    png.insert_chunk_before_iend(chunk)?;
 
     let (mut write_target_file, write_target_file_path) = match output_file_path {
         Some(alt_path) => (open_file_for_rewrite(&alt_path)?, alt_path),
@@ -111,11 +164,34 @@ fn handle_encode_command(
     Ok(())
 }
 
-fn handle_decode_command(file_path: String, chunk_type: String) -> anyhow::Result<()> {
+fn format_meta_value(value: &MetaValue) -> String {
+    match value {
+        MetaValue::String(string) => string.clone(),
+        MetaValue::Int(int) => int.to_string(),
+        MetaValue::Bool(bool) => bool.to_string(),
+        MetaValue::Bytes(bytes) => base64_codec::encode(bytes),
+    }
+}
+
+fn handle_decode_command(
+    file_path: String,
+    chunk_type: String,
+    base64: bool,
+    meta: bool,
+) -> anyhow::Result<()> {
     let mut file = File::open(file_path)?;
     let png = create_png_from_file_bytes(&mut file)?;
 
     match png.chunk_by_type(&chunk_type) {
+        Some(chunk) if meta => match chunk.metadata() {
+            Ok(header) => {
+                for (key, value) in &header {
+                    println!("{key}: {}", format_meta_value(value));
+                }
+            }
+            Err(_) => println!("couldn't decode metadata from this chunk"),
+        },
+        Some(chunk) if base64 => println!("{}", chunk.data_as_base64()),
         Some(chunk) => match chunk.data_as_string() {
             Ok(message) => println!("{message}"),
             Err(_) => println!(
@@ -163,11 +239,15 @@ fn main() {
             chunk_type,
             message,
             output_file_path,
-        } => handle_encode_command(file_path, chunk_type, message, output_file_path),
+            input_file,
+            meta,
+        } => handle_encode_command(file_path, chunk_type, message, output_file_path, input_file, meta),
         Commands::Decode {
             file_path,
             chunk_type,
-        } => handle_decode_command(file_path, chunk_type),
+            base64,
+            meta,
+        } => handle_decode_command(file_path, chunk_type, base64, meta),
         Commands::Remove {
             file_path,
             chunk_type,
@@ -0,0 +1,267 @@
+use std::collections::BTreeMap;
+
+use thiserror::Error;
+
+use super::byte_reader::{ByteReader, ByteReaderError};
+
+/// A structured payload attached to a message chunk: named fields instead of
+/// one opaque string.
+pub type Header = BTreeMap<String, MetaValue>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MetaValue {
+    String(String),
+    Int(i64),
+    Bool(bool),
+    Bytes(Vec<u8>),
+}
+
+#[derive(Error, Debug)]
+pub enum MetadataDecodeError {
+    #[error(transparent)]
+    OutOfBounds(#[from] ByteReaderError),
+    #[error("invalid utf-8 string in metadata")]
+    InvalidString,
+    #[error("expected a string key in metadata map")]
+    ExpectedStringKey,
+    #[error("unsupported MessagePack marker byte {0:#04x}")]
+    UnsupportedMarker(u8),
+}
+
+#[derive(Error, Debug)]
+pub enum MetadataEncodeError {
+    #[error("metadata value of {len} bytes exceeds the maximum of {max}")]
+    ValueTooLarge { len: usize, max: usize },
+}
+
+const FIXMAP_MARKER: u8 = 0x80;
+const MAP16_MARKER: u8 = 0xde;
+const FIXSTR_MARKER: u8 = 0xa0;
+const STR8_MARKER: u8 = 0xd9;
+const STR16_MARKER: u8 = 0xda;
+const BIN8_MARKER: u8 = 0xc4;
+const BIN16_MARKER: u8 = 0xc5;
+const FALSE_MARKER: u8 = 0xc2;
+const TRUE_MARKER: u8 = 0xc3;
+const INT64_MARKER: u8 = 0xd3;
+
+fn encode_str(out: &mut Vec<u8>, value: &str) -> Result<(), MetadataEncodeError> {
+    let bytes = value.as_bytes();
+
+    if bytes.len() <= 31 {
+        out.push(FIXSTR_MARKER | bytes.len() as u8);
+    } else if bytes.len() <= u8::MAX as usize {
+        out.push(STR8_MARKER);
+        out.push(bytes.len() as u8);
+    } else if bytes.len() <= u16::MAX as usize {
+        out.push(STR16_MARKER);
+        out.extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+    } else {
+        return Err(MetadataEncodeError::ValueTooLarge {
+            len: bytes.len(),
+            max: u16::MAX as usize,
+        });
+    }
+
+    out.extend_from_slice(bytes);
+
+    Ok(())
+}
+
+fn encode_value(out: &mut Vec<u8>, value: &MetaValue) -> Result<(), MetadataEncodeError> {
+    match value {
+        MetaValue::String(string) => encode_str(out, string)?,
+        MetaValue::Bool(bool) => out.push(if *bool { TRUE_MARKER } else { FALSE_MARKER }),
+        MetaValue::Int(int) => {
+            if (0..128).contains(int) {
+                out.push(*int as u8);
+            } else {
+                out.push(INT64_MARKER);
+                out.extend_from_slice(&int.to_be_bytes());
+            }
+        }
+        MetaValue::Bytes(bytes) => {
+            if bytes.len() <= u8::MAX as usize {
+                out.push(BIN8_MARKER);
+                out.push(bytes.len() as u8);
+            } else if bytes.len() <= u16::MAX as usize {
+                out.push(BIN16_MARKER);
+                out.extend_from_slice(&(bytes.len() as u16).to_be_bytes());
+            } else {
+                return Err(MetadataEncodeError::ValueTooLarge {
+                    len: bytes.len(),
+                    max: u16::MAX as usize,
+                });
+            }
+            out.extend_from_slice(bytes);
+        }
+    }
+
+    Ok(())
+}
+
+/// Serializes `header` into a compact MessagePack-style encoding.
+pub fn encode(header: &Header) -> Result<Vec<u8>, MetadataEncodeError> {
+    let mut out = Vec::new();
+
+    if header.len() <= 15 {
+        out.push(FIXMAP_MARKER | header.len() as u8);
+    } else {
+        out.push(MAP16_MARKER);
+        out.extend_from_slice(&(header.len() as u16).to_be_bytes());
+    }
+
+    for (key, value) in header {
+        encode_str(&mut out, key)?;
+        encode_value(&mut out, value)?;
+    }
+
+    Ok(out)
+}
+
+fn decode_value(reader: &mut ByteReader) -> Result<MetaValue, MetadataDecodeError> {
+    let marker = reader.take(1)?[0];
+
+    match marker {
+        FALSE_MARKER => Ok(MetaValue::Bool(false)),
+        TRUE_MARKER => Ok(MetaValue::Bool(true)),
+        INT64_MARKER => {
+            let bytes: [u8; 8] = reader.take(8)?.try_into().unwrap();
+            Ok(MetaValue::Int(i64::from_be_bytes(bytes)))
+        }
+        BIN8_MARKER => {
+            let len = reader.take(1)?[0] as usize;
+            Ok(MetaValue::Bytes(reader.take(len)?.to_vec()))
+        }
+        BIN16_MARKER => {
+            let len_bytes: [u8; 2] = reader.take(2)?.try_into().unwrap();
+            let len = u16::from_be_bytes(len_bytes) as usize;
+            Ok(MetaValue::Bytes(reader.take(len)?.to_vec()))
+        }
+        STR8_MARKER => {
+            let len = reader.take(1)?[0] as usize;
+            decode_str_body(reader, len)
+        }
+        STR16_MARKER => {
+            let len_bytes: [u8; 2] = reader.take(2)?.try_into().unwrap();
+            decode_str_body(reader, u16::from_be_bytes(len_bytes) as usize)
+        }
+        marker if marker & 0x80 == 0 => Ok(MetaValue::Int(marker as i64)),
+        marker if marker & 0xe0 == FIXSTR_MARKER => decode_str_body(reader, (marker & 0x1f) as usize),
+        marker => Err(MetadataDecodeError::UnsupportedMarker(marker)),
+    }
+}
+
+fn decode_str_body(reader: &mut ByteReader, len: usize) -> Result<MetaValue, MetadataDecodeError> {
+    String::from_utf8(reader.take(len)?.to_vec())
+        .map(MetaValue::String)
+        .map_err(|_| MetadataDecodeError::InvalidString)
+}
+
+fn decode_key(reader: &mut ByteReader) -> Result<String, MetadataDecodeError> {
+    match decode_value(reader)? {
+        MetaValue::String(key) => Ok(key),
+        _ => Err(MetadataDecodeError::ExpectedStringKey),
+    }
+}
+
+/// Reads back a [`Header`] serialized with [`encode`].
+pub fn decode(bytes: &[u8]) -> Result<Header, MetadataDecodeError> {
+    let mut reader = ByteReader::new(bytes);
+    let marker = reader.take(1)?[0];
+
+    let len = match marker {
+        MAP16_MARKER => {
+            let bytes: [u8; 2] = reader.take(2)?.try_into().unwrap();
+            u16::from_be_bytes(bytes) as usize
+        }
+        marker if marker & 0xf0 == FIXMAP_MARKER => (marker & 0x0f) as usize,
+        marker => return Err(MetadataDecodeError::UnsupportedMarker(marker)),
+    };
+
+    let mut header = Header::new();
+
+    for _ in 0..len {
+        let key = decode_key(&mut reader)?;
+        let value = decode_value(&mut reader)?;
+        header.insert(key, value);
+    }
+
+    Ok(header)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_mixed_values() {
+        let mut header = Header::new();
+        header.insert("author".to_string(), MetaValue::String("rust".to_string()));
+        header.insert("timestamp".to_string(), MetaValue::Int(1_700_000_000));
+        header.insert("draft".to_string(), MetaValue::Bool(false));
+        header.insert("thumbnail".to_string(), MetaValue::Bytes(vec![1, 2, 3]));
+
+        let encoded = encode(&header).unwrap();
+        let decoded = decode(&encoded).unwrap();
+
+        assert_eq!(decoded, header);
+    }
+
+    #[test]
+    fn test_roundtrip_empty_header() {
+        let header = Header::new();
+        assert_eq!(decode(&encode(&header).unwrap()).unwrap(), header);
+    }
+
+    #[test]
+    fn test_roundtrip_negative_int() {
+        let mut header = Header::new();
+        header.insert("offset".to_string(), MetaValue::Int(-42));
+
+        assert_eq!(decode(&encode(&header).unwrap()).unwrap(), header);
+    }
+
+    #[test]
+    fn test_decode_rejects_unsupported_marker() {
+        assert!(decode(&[0xc1]).is_err());
+    }
+
+    #[test]
+    fn test_roundtrip_string_over_255_bytes() {
+        let mut header = Header::new();
+        header.insert("blob".to_string(), MetaValue::String("a".repeat(300)));
+
+        assert_eq!(decode(&encode(&header).unwrap()).unwrap(), header);
+    }
+
+    #[test]
+    fn test_roundtrip_bytes_over_255_bytes() {
+        let mut header = Header::new();
+        header.insert("thumbnail".to_string(), MetaValue::Bytes(vec![7; 300]));
+
+        assert_eq!(decode(&encode(&header).unwrap()).unwrap(), header);
+    }
+
+    #[test]
+    fn test_encode_rejects_string_over_u16_max_bytes() {
+        let mut header = Header::new();
+        header.insert(
+            "blob".to_string(),
+            MetaValue::String("a".repeat(u16::MAX as usize + 1)),
+        );
+
+        assert!(encode(&header).is_err());
+    }
+
+    #[test]
+    fn test_encode_rejects_bytes_over_u16_max_bytes() {
+        let mut header = Header::new();
+        header.insert(
+            "thumbnail".to_string(),
+            MetaValue::Bytes(vec![7; u16::MAX as usize + 1]),
+        );
+
+        assert!(encode(&header).is_err());
+    }
+}
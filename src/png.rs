@@ -0,0 +1,252 @@
+use std::fmt::{self, Display};
+
+use thiserror::Error;
+
+use super::chunk::{PngChunk, PngChunkParsingError};
+
+pub const PNG_HEADER: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+#[derive(Debug)]
+pub struct Png {
+    chunks: Vec<PngChunk>,
+}
+
+#[derive(Error, Debug)]
+pub enum PngParsingError {
+    #[error("expected PNG header {PNG_HEADER:?}, got {0:?}")]
+    InvalidHeader(Vec<u8>),
+    #[error(transparent)]
+    InvalidChunk(#[from] PngChunkParsingError),
+}
+
+#[derive(Error, Debug)]
+pub enum PngChunkLookupError {
+    #[error("no chunk with type {0} found")]
+    NotFound(String),
+}
+
+#[derive(Error, Debug)]
+pub enum PngChunkPlacementError {
+    #[error("chunk type {0} is critical and cannot be used for a hidden message")]
+    CriticalChunkType(String),
+    #[error("png has no IHDR chunk to place the message chunk after")]
+    MissingHeader,
+    #[error("png has no IEND chunk to place the message chunk before")]
+    MissingEnd,
+}
+
+impl TryFrom<&[u8]> for Png {
+    type Error = PngParsingError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        if bytes.len() < PNG_HEADER.len() || bytes[..PNG_HEADER.len()] != PNG_HEADER {
+            return Err(PngParsingError::InvalidHeader(
+                bytes.get(..PNG_HEADER.len()).unwrap_or(bytes).to_vec(),
+            ));
+        }
+
+        let mut chunks = Vec::new();
+        let mut remaining = &bytes[PNG_HEADER.len()..];
+
+        while !remaining.is_empty() {
+            let chunk = PngChunk::try_from(remaining)?;
+            remaining = &remaining[chunk.as_bytes().len()..];
+            chunks.push(chunk);
+        }
+
+        Ok(Png { chunks })
+    }
+}
+
+impl Display for Png {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for chunk in &self.chunks {
+            writeln!(f, "{chunk}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Png {
+    pub fn from_chunks(chunks: Vec<PngChunk>) -> Png {
+        Png { chunks }
+    }
+
+    pub fn chunks(&self) -> &[PngChunk] {
+        &self.chunks
+    }
+
+    pub fn append_chunk(&mut self, chunk: PngChunk) {
+        self.chunks.push(chunk);
+    }
+
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&PngChunk> {
+        self.chunks
+            .iter()
+            .find(|chunk| chunk.chunk_type.to_string() == chunk_type)
+    }
+
+    pub fn remove_first_chunk(
+        &mut self,
+        chunk_type: &str,
+    ) -> Result<PngChunk, PngChunkLookupError> {
+        let position = self
+            .chunks
+            .iter()
+            .position(|chunk| chunk.chunk_type.to_string() == chunk_type)
+            .ok_or_else(|| PngChunkLookupError::NotFound(chunk_type.to_string()))?;
+
+        Ok(self.chunks.remove(position))
+    }
+
+    /// Inserts `chunk` right before the `IEND` chunk, keeping it after `IHDR`
+    /// and out of the `IDAT` run so it survives being passed through a
+    /// conformant PNG editor.
+    pub fn insert_chunk_before_iend(
+        &mut self,
+        chunk: PngChunk,
+    ) -> Result<(), PngChunkPlacementError> {
+        if chunk.chunk_type.is_critical() {
+            return Err(PngChunkPlacementError::CriticalChunkType(
+                chunk.chunk_type.to_string(),
+            ));
+        }
+
+        self.chunks
+            .iter()
+            .find(|chunk| chunk.chunk_type.to_string() == "IHDR")
+            .ok_or(PngChunkPlacementError::MissingHeader)?;
+
+        let end_position = self
+            .chunks
+            .iter()
+            .position(|chunk| chunk.chunk_type.to_string() == "IEND")
+            .ok_or(PngChunkPlacementError::MissingEnd)?;
+
+        self.chunks.insert(end_position, chunk);
+
+        Ok(())
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        PNG_HEADER
+            .iter()
+            .copied()
+            .chain(self.chunks.iter().flat_map(PngChunk::as_bytes))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::PngChunkType;
+    use std::str::FromStr;
+
+    fn testing_png() -> Png {
+        let chunk_type = PngChunkType::from_str("RuSt").unwrap();
+        let data = "This is where your secret message will be!"
+            .as_bytes()
+            .to_vec();
+
+        Png::from_chunks(vec![PngChunk::new(chunk_type, data)])
+    }
+
+    #[test]
+    fn test_png_from_bytes_roundtrip() {
+        let png = testing_png();
+        let bytes = png.as_bytes();
+        let decoded = Png::try_from(bytes.as_ref()).unwrap();
+
+        assert_eq!(decoded.chunks().len(), 1);
+    }
+
+    #[test]
+    fn test_png_from_bytes_rejects_bad_header() {
+        let bytes = vec![0, 1, 2, 3, 4, 5, 6, 7];
+        assert!(Png::try_from(bytes.as_ref()).is_err());
+    }
+
+    #[test]
+    fn test_png_append_and_lookup_chunk() {
+        let mut png = Png::from_chunks(Vec::new());
+        let chunk_type = PngChunkType::from_str("RuSt").unwrap();
+        png.append_chunk(PngChunk::new(chunk_type, b"hello".to_vec()));
+
+        assert!(png.chunk_by_type("RuSt").is_some());
+    }
+
+    #[test]
+    fn test_png_remove_first_chunk() {
+        let mut png = testing_png();
+        let removed = png.remove_first_chunk("RuSt").unwrap();
+
+        assert_eq!(removed.chunk_type.to_string(), "RuSt");
+        assert!(png.chunk_by_type("RuSt").is_none());
+    }
+
+    #[test]
+    fn test_png_remove_first_chunk_not_found() {
+        let mut png = testing_png();
+        assert!(png.remove_first_chunk("NoNo").is_err());
+    }
+
+    fn png_with_structure() -> Png {
+        Png::from_chunks(vec![
+            PngChunk::new(PngChunkType::from_str("IHDR").unwrap(), Vec::new()),
+            PngChunk::new(PngChunkType::from_str("IDAT").unwrap(), Vec::new()),
+            PngChunk::new(PngChunkType::from_str("IEND").unwrap(), Vec::new()),
+        ])
+    }
+
+    #[test]
+    fn test_insert_chunk_before_iend() {
+        let mut png = png_with_structure();
+        let chunk_type = PngChunkType::from_str("ruSt").unwrap();
+        png.insert_chunk_before_iend(PngChunk::new(chunk_type, b"hidden".to_vec()))
+            .unwrap();
+
+        let types: Vec<String> = png.chunks().iter().map(|c| c.chunk_type.to_string()).collect();
+        assert_eq!(types, vec!["IHDR", "IDAT", "ruSt", "IEND"]);
+    }
+
+    #[test]
+    fn test_insert_chunk_before_iend_rejects_critical_type() {
+        let mut png = png_with_structure();
+        let chunk_type = PngChunkType::from_str("RuSt").unwrap();
+
+        assert!(
+            png.insert_chunk_before_iend(PngChunk::new(chunk_type, b"hidden".to_vec()))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_insert_chunk_before_iend_requires_ihdr() {
+        let mut png = Png::from_chunks(vec![PngChunk::new(
+            PngChunkType::from_str("IEND").unwrap(),
+            Vec::new(),
+        )]);
+        let chunk_type = PngChunkType::from_str("ruSt").unwrap();
+
+        assert!(
+            png.insert_chunk_before_iend(PngChunk::new(chunk_type, b"hidden".to_vec()))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_insert_chunk_before_iend_requires_iend() {
+        let mut png = Png::from_chunks(vec![PngChunk::new(
+            PngChunkType::from_str("IHDR").unwrap(),
+            Vec::new(),
+        )]);
+        let chunk_type = PngChunkType::from_str("ruSt").unwrap();
+
+        assert!(
+            png.insert_chunk_before_iend(PngChunk::new(chunk_type, b"hidden".to_vec()))
+                .is_err()
+        );
+    }
+}
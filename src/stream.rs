@@ -0,0 +1,310 @@
+use crc32fast::Hasher;
+
+use super::chunk::{CRC_LENGTH, PNG_CHUNK_DATA_LENGTH_LENGTH, PNG_CHUNK_MINIMUM_LENGTH, PngChunk};
+use super::chunk_type::{
+    PNG_CHUNK_TYPE_LENGTH, PngChunkType, PngChunkTypeBinaryData, PngChunkTypeParsingError,
+};
+
+/// Something the decoder observed while consuming pushed bytes.
+#[derive(Debug)]
+pub enum StreamEvent {
+    /// A chunk's length and type have been read; its data is still incoming.
+    ChunkBegin {
+        chunk_type: PngChunkTypeBinaryData,
+        length: u32,
+    },
+    /// A chunk's data and CRC have been read and the CRC matched.
+    ChunkComplete(PngChunk),
+    /// A chunk's CRC did not match the bytes it covers.
+    ///
+    /// The chunk is not emitted; `recover` is how many bytes made up the
+    /// malformed framing (length + type + data + crc), which the decoder has
+    /// already consumed. Decoding resumes right after the stored CRC, reading
+    /// the following bytes as the next chunk's length; no search for a chunk
+    /// boundary is made.
+    CrcMismatch {
+        stored: u32,
+        computed: u32,
+        recover: usize,
+    },
+    /// The CRC matched, but the chunk type bytes are not valid PNG chunk
+    /// type characters (ASCII letters only), so no `PngChunk` could be built.
+    InvalidChunkType {
+        chunk_type: PngChunkTypeBinaryData,
+        error: PngChunkTypeParsingError,
+    },
+    /// An `IEND` chunk was decoded; no further chunks are expected.
+    End,
+}
+
+enum State {
+    Length,
+    Type {
+        length: u32,
+    },
+    Data {
+        length: u32,
+        chunk_type: PngChunkTypeBinaryData,
+        data: Vec<u8>,
+    },
+    Crc {
+        length: u32,
+        chunk_type: PngChunkTypeBinaryData,
+        data: Vec<u8>,
+    },
+}
+
+/// Decodes a byte stream into [`PngChunk`]s incrementally, without requiring
+/// the whole file to be buffered in memory up front.
+///
+/// Bytes are fed in via [`push`](ChunkStreamDecoder::push) as they become
+/// available (e.g. from a `Read`); each call returns the events that became
+/// decodable from the newly accumulated bytes.
+pub struct ChunkStreamDecoder {
+    state: State,
+    buffer: Vec<u8>,
+}
+
+impl Default for ChunkStreamDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChunkStreamDecoder {
+    pub fn new() -> ChunkStreamDecoder {
+        ChunkStreamDecoder {
+            state: State::Length,
+            buffer: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<StreamEvent> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+
+        while let Some(new_events) = self.advance() {
+            events.extend(new_events);
+        }
+
+        events
+    }
+
+    fn advance(&mut self) -> Option<Vec<StreamEvent>> {
+        match std::mem::replace(&mut self.state, State::Length) {
+            State::Length => {
+                if self.buffer.len() < PNG_CHUNK_DATA_LENGTH_LENGTH {
+                    self.state = State::Length;
+                    return None;
+                }
+
+                let length_bytes: [u8; 4] = self
+                    .buffer
+                    .drain(..PNG_CHUNK_DATA_LENGTH_LENGTH)
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap();
+                let length = u32::from_be_bytes(length_bytes);
+
+                self.state = State::Type { length };
+                self.advance()
+            }
+            State::Type { length } => {
+                if self.buffer.len() < PNG_CHUNK_TYPE_LENGTH {
+                    self.state = State::Type { length };
+                    return None;
+                }
+
+                let chunk_type: PngChunkTypeBinaryData = self
+                    .buffer
+                    .drain(..PNG_CHUNK_TYPE_LENGTH)
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap();
+
+                self.state = State::Data {
+                    length,
+                    chunk_type,
+                    data: Vec::new(),
+                };
+
+                Some(vec![StreamEvent::ChunkBegin { chunk_type, length }])
+            }
+            State::Data {
+                length,
+                chunk_type,
+                mut data,
+            } => {
+                let remaining = length as usize - data.len();
+
+                if remaining > 0 {
+                    if self.buffer.is_empty() {
+                        self.state = State::Data {
+                            length,
+                            chunk_type,
+                            data,
+                        };
+                        return None;
+                    }
+
+                    let take = remaining.min(self.buffer.len());
+                    data.extend(self.buffer.drain(..take));
+                }
+
+                if data.len() < length as usize {
+                    self.state = State::Data {
+                        length,
+                        chunk_type,
+                        data,
+                    };
+                    return None;
+                }
+
+                self.state = State::Crc {
+                    length,
+                    chunk_type,
+                    data,
+                };
+                self.advance()
+            }
+            State::Crc {
+                length,
+                chunk_type,
+                data,
+            } => {
+                if self.buffer.len() < CRC_LENGTH {
+                    self.state = State::Crc {
+                        length,
+                        chunk_type,
+                        data,
+                    };
+                    return None;
+                }
+
+                let stored_bytes: [u8; 4] = self
+                    .buffer
+                    .drain(..CRC_LENGTH)
+                    .collect::<Vec<_>>()
+                    .try_into()
+                    .unwrap();
+                let stored = u32::from_be_bytes(stored_bytes);
+
+                let mut hasher = Hasher::new();
+                hasher.update(&chunk_type);
+                hasher.update(&data);
+                let computed = hasher.finalize();
+
+                self.state = State::Length;
+
+                if stored != computed {
+                    return Some(vec![StreamEvent::CrcMismatch {
+                        stored,
+                        computed,
+                        recover: PNG_CHUNK_MINIMUM_LENGTH + data.len(),
+                    }]);
+                }
+
+                let parsed_type = match PngChunkType::try_from(chunk_type) {
+                    Ok(parsed_type) => parsed_type,
+                    Err(error) => {
+                        return Some(vec![StreamEvent::InvalidChunkType { chunk_type, error }]);
+                    }
+                };
+
+                let is_end = chunk_type == *b"IEND";
+                let chunk = PngChunk::new(parsed_type, data);
+
+                let mut events = vec![StreamEvent::ChunkComplete(chunk)];
+                if is_end {
+                    events.push(StreamEvent::End);
+                }
+
+                Some(events)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_type::PngChunkType as ChunkType;
+    use std::str::FromStr;
+
+    fn chunk_bytes(chunk_type: &str, data: &[u8]) -> Vec<u8> {
+        PngChunk::new(ChunkType::from_str(chunk_type).unwrap(), data.to_vec()).as_bytes()
+    }
+
+    /// Builds raw chunk bytes with a correct CRC but without validating the
+    /// chunk type, for exercising types `PngChunkType::from_str` would reject.
+    fn chunk_bytes_with_raw_type(chunk_type: PngChunkTypeBinaryData, data: &[u8]) -> Vec<u8> {
+        let mut hasher = Hasher::new();
+        hasher.update(&chunk_type);
+        hasher.update(data);
+        let crc = hasher.finalize();
+
+        (data.len() as u32)
+            .to_be_bytes()
+            .iter()
+            .chain(chunk_type.iter())
+            .chain(data.iter())
+            .chain(crc.to_be_bytes().iter())
+            .copied()
+            .collect()
+    }
+
+    #[test]
+    fn test_decodes_chunk_fed_in_one_push() {
+        let mut decoder = ChunkStreamDecoder::new();
+        let events = decoder.push(&chunk_bytes("RuSt", b"hello"));
+
+        assert!(matches!(events[0], StreamEvent::ChunkBegin { length: 5, .. }));
+        assert!(matches!(events[1], StreamEvent::ChunkComplete(ref chunk) if chunk.data == b"hello"));
+    }
+
+    #[test]
+    fn test_decodes_chunk_fed_byte_by_byte() {
+        let mut decoder = ChunkStreamDecoder::new();
+        let bytes = chunk_bytes("RuSt", b"hello");
+        let mut events = Vec::new();
+
+        for byte in bytes {
+            events.extend(decoder.push(&[byte]));
+        }
+
+        assert!(matches!(events[0], StreamEvent::ChunkBegin { length: 5, .. }));
+        assert!(matches!(events[1], StreamEvent::ChunkComplete(ref chunk) if chunk.data == b"hello"));
+    }
+
+    #[test]
+    fn test_emits_end_after_iend_chunk() {
+        let mut decoder = ChunkStreamDecoder::new();
+        let events = decoder.push(&chunk_bytes("IEND", b""));
+
+        assert!(matches!(events.last(), Some(StreamEvent::End)));
+    }
+
+    #[test]
+    fn test_crc_mismatch_recovers_and_continues() {
+        let mut decoder = ChunkStreamDecoder::new();
+        let mut bytes = chunk_bytes("RuSt", b"corrupted");
+        let crc_start = bytes.len() - CRC_LENGTH;
+        bytes[crc_start] ^= 0xff;
+        bytes.extend(chunk_bytes("RuSt", b"next"));
+
+        let events = decoder.push(&bytes);
+
+        assert!(matches!(events[1], StreamEvent::CrcMismatch { .. }));
+        assert!(matches!(events[2], StreamEvent::ChunkBegin { length: 4, .. }));
+        assert!(matches!(events[3], StreamEvent::ChunkComplete(ref chunk) if chunk.data == b"next"));
+    }
+
+    #[test]
+    fn test_rejects_chunk_type_with_non_ascii_letters() {
+        let mut decoder = ChunkStreamDecoder::new();
+        let events = decoder.push(&chunk_bytes_with_raw_type(*b"1DAT", b"hello"));
+
+        assert!(matches!(events[1], StreamEvent::InvalidChunkType { chunk_type, .. } if &chunk_type == b"1DAT"));
+    }
+}